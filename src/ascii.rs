@@ -0,0 +1,147 @@
+//! ASCII export of the decoded time series.
+//!
+//! Once the Steim1 body has been decoded, [`NpPacket::to_slist`] and
+//! [`NpPacket::to_geocsv`] dump the waveform to human-inspectable text — a
+//! dependency-free way to QC a capture without building MiniSEED. Both formats
+//! are common in the seismic ecosystem: SLIST is the IRIS sample list and
+//! GeoCSV is the headered CSV used by IRIS/FDSN tools.
+
+use std::io::{self, Write};
+
+use crate::{steim1, time, NpPacket};
+
+/// Decodes the payload body, surfacing a Steim1 failure as an I/O error so it
+/// composes with the `Write`-based API.
+fn decode_samples(packet: &NpPacket) -> io::Result<(Vec<i32>, u16, u16)> {
+    let number_samples =
+        u16::from_be_bytes([packet.payload.header.number_samples[2], packet.payload.header.number_samples[3]]);
+    let sample_rate =
+        u16::from_be_bytes([packet.payload.header.sample_rate[2], packet.payload.header.sample_rate[3]]);
+    let samples = steim1::decode(&packet.payload.body, u32::from(number_samples))
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok((samples, number_samples, sample_rate))
+}
+
+impl NpPacket {
+    /// Writes the waveform as an IRIS SLIST record.
+    ///
+    /// The header line carries the `NET_STA_LOC_CHAN` identifier, sample
+    /// count, sample rate and start time; the samples follow, six per line.
+    pub fn to_slist<W: Write>(
+        &self,
+        station: &str,
+        network: &str,
+        channel: &str,
+        mut writer: W,
+    ) -> io::Result<()> {
+        let (samples, number_samples, sample_rate) = decode_samples(self)?;
+        let start = time::format_timestamp(self.header.start_time);
+        writeln!(
+            writer,
+            "TIMESERIES {network}_{station}__{channel}, {number_samples} samples, {sample_rate} sps, {start}, SLIST, INTEGER, Counts"
+        )?;
+        for chunk in samples.chunks(6) {
+            let line: Vec<String> = chunk.iter().map(|s| s.to_string()).collect();
+            writeln!(writer, "{}", line.join("\t"))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the waveform as a GeoCSV 2.0 record.
+    ///
+    /// The header is a block of `# key: value` lines describing the stream,
+    /// followed by one integer sample per line under a `Sample` column.
+    pub fn to_geocsv<W: Write>(
+        &self,
+        station: &str,
+        network: &str,
+        channel: &str,
+        mut writer: W,
+    ) -> io::Result<()> {
+        let (samples, number_samples, sample_rate) = decode_samples(self)?;
+        let start = time::format_timestamp(self.header.start_time);
+        writeln!(writer, "# dataset: GeoCSV 2.0")?;
+        writeln!(writer, "# delimiter: ,")?;
+        writeln!(writer, "# field_unit: counts")?;
+        writeln!(writer, "# field_type: integer")?;
+        writeln!(writer, "# network: {network}")?;
+        writeln!(writer, "# station: {station}")?;
+        writeln!(writer, "# channel: {channel}")?;
+        writeln!(writer, "# start_time: {start}")?;
+        writeln!(writer, "# sample_rate_hz: {sample_rate}")?;
+        writeln!(writer, "# sample_count: {number_samples}")?;
+        writeln!(writer, "Sample")?;
+        for sample in samples {
+            writeln!(writer, "{sample}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{NpExtensionBlock, NpHeader, NpPayload, NpPayloadHeader};
+
+    fn packet_with_samples(samples: &[i32]) -> NpPacket {
+        let mut body = steim1::encode(samples);
+        body.resize(3 * 64, 0);
+        NpPacket {
+            header: NpHeader {
+                np_version: [0x4E, 0x50],
+                packet_size: 243,
+                sequence_number: 0,
+                meta_sequence_number: -1,
+                start_time: 0,
+                latitude: 0,
+                longitude: 0,
+                altitude: 0,
+                data_source: [0xE8, 11, 0, 1],
+                band_name: 0x89,
+                packet_extension_block: NpExtensionBlock::default(),
+            },
+            payload: NpPayload {
+                header: NpPayloadHeader {
+                    payload_size: 206,
+                    payload_name: 0,
+                    payload_media_type: 0x83,
+                    payload_extension_block: NpExtensionBlock::default(),
+                    number_samples: [0x05, 0x87, 0, samples.len() as u8],
+                    sample_rate: [0x05, 0x85, 0, 100],
+                },
+                body,
+            },
+        }
+    }
+
+    #[test]
+    fn slist_header_and_samples_round_trip() {
+        let packet = packet_with_samples(&[1, 2, 3, 4]);
+        let mut out = Vec::new();
+        packet.to_slist("STA", "NET", "CHZ", &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next().unwrap(),
+            format!(
+                "TIMESERIES NET_STA__CHZ, 4 samples, 100 sps, {}, SLIST, INTEGER, Counts",
+                time::format_timestamp(0)
+            )
+        );
+        assert_eq!(lines.next().unwrap(), "1\t2\t3\t4");
+    }
+
+    #[test]
+    fn geocsv_header_and_samples_round_trip() {
+        let packet = packet_with_samples(&[10, 20]);
+        let mut out = Vec::new();
+        packet.to_geocsv("STA", "NET", "CHZ", &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "# dataset: GeoCSV 2.0");
+        assert_eq!(lines[8], "# sample_rate_hz: 100");
+        assert_eq!(lines[9], "# sample_count: 2");
+        assert_eq!(lines[10], "Sample");
+        assert_eq!(&lines[11..], &["10", "20"]);
+    }
+}
@@ -0,0 +1,297 @@
+//! Byte-level parsing and serialization for the NP packet structures.
+//!
+//! Every field follows the documented big-endian layout. A seismic data
+//! packet is laid out as a 37 byte [`NpHeader`](crate::NpHeader), a 14 byte
+//! [`NpPayloadHeader`](crate::NpPayloadHeader), and then the Steim1 body, so
+//! the body starts at offset `51` and the whole packet is `499` bytes (7
+//! frames) or `243` bytes (3 frames).
+
+use crate::{NpExtensionBlock, NpHeader, NpPacket, NpPayload, NpPayloadHeader};
+
+/// ASCII `NP`, the magic that opens every packet.
+const NP_MAGIC: [u8; 2] = [0x4E, 0x50];
+/// Packet sizes for the two seismic frame counts (7 and 3 frames).
+const PACKET_SIZES: [u16; 2] = [499, 243];
+/// Payload sizes for the two seismic frame counts (7 and 3 frames).
+const PAYLOAD_SIZES: [u16; 2] = [462, 206];
+/// Media type of a Steim1 encoded time-series payload.
+const MEDIA_STEIM1: u8 = 0x83;
+/// Tag preceding the `numSamples` value.
+const TAG_NUM_SAMPLES: u16 = 0x0587;
+/// Tag preceding the `sampleRate` value.
+const TAG_SAMPLE_RATE: u16 = 0x0585;
+
+/// Offset at which the payload body (Steim1 frames) begins.
+const BODY_OFFSET: usize = 51;
+
+/// Errors produced while reading an NP packet from bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NpError {
+    /// The packet did not begin with the `NP` magic.
+    BadMagic([u8; 2]),
+    /// The input ended before a field could be read in full.
+    Truncated { offset: usize, needed: usize, have: usize },
+    /// A declared size field disagreed with the documented values or with
+    /// another size field. Input shorter than a declared size is reported as
+    /// [`NpError::Truncated`]; bytes past the declared packet are not checked.
+    InconsistentSize { field: &'static str, value: u16 },
+    /// A tag-prefixed field carried an unexpected tag.
+    BadTag { field: &'static str, expected: u16, found: u16 },
+}
+
+impl std::fmt::Display for NpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NpError::BadMagic(m) => write!(f, "bad magic: expected NP, found {m:02X?}"),
+            NpError::Truncated { offset, needed, have } => write!(
+                f,
+                "truncated input at offset {offset}: needed {needed} bytes, have {have}"
+            ),
+            NpError::InconsistentSize { field, value } => {
+                write!(f, "inconsistent size field {field}: {value}")
+            }
+            NpError::BadTag { field, expected, found } => write!(
+                f,
+                "bad tag on field {field}: expected {expected:#06X}, found {found:#06X}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NpError {}
+
+/// A bounds-checked cursor over the input bytes.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], NpError> {
+        if self.pos + n > self.bytes.len() {
+            return Err(NpError::Truncated {
+                offset: self.pos,
+                needed: n,
+                have: self.bytes.len() - self.pos.min(self.bytes.len()),
+            });
+        }
+        let slice = &self.bytes[self.pos..self.pos + n];
+        self.pos += n;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], NpError> {
+        let slice = self.take(N)?;
+        let mut out = [0u8; N];
+        out.copy_from_slice(slice);
+        Ok(out)
+    }
+
+    fn u16(&mut self) -> Result<u16, NpError> {
+        Ok(u16::from_be_bytes(self.array()?))
+    }
+
+    fn i16(&mut self) -> Result<i16, NpError> {
+        Ok(i16::from_be_bytes(self.array()?))
+    }
+
+    fn i32(&mut self) -> Result<i32, NpError> {
+        Ok(i32::from_be_bytes(self.array()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, NpError> {
+        Ok(u64::from_be_bytes(self.array()?))
+    }
+
+    fn u8(&mut self) -> Result<u8, NpError> {
+        Ok(self.array::<1>()?[0])
+    }
+}
+
+impl NpHeader {
+    /// Parses an [`NpHeader`] from the start of `bytes`, leaving the reader
+    /// positioned at the payload.
+    fn read(r: &mut Reader<'_>) -> Result<NpHeader, NpError> {
+        let np_version: [u8; 2] = r.array()?;
+        if np_version != NP_MAGIC {
+            return Err(NpError::BadMagic(np_version));
+        }
+        let packet_size = r.u16()?;
+        if !PACKET_SIZES.contains(&packet_size) {
+            return Err(NpError::InconsistentSize {
+                field: "packet_size",
+                value: packet_size,
+            });
+        }
+        Ok(NpHeader {
+            np_version,
+            packet_size,
+            sequence_number: r.i32()?,
+            meta_sequence_number: r.i32()?,
+            start_time: r.u64()?,
+            latitude: r.i32()?,
+            longitude: r.i32()?,
+            altitude: r.i16()?,
+            data_source: r.array()?,
+            band_name: r.u8()?,
+            packet_extension_block: NpExtensionBlock::from_bytes(r.take(2)?),
+        })
+    }
+
+    /// Parses an [`NpHeader`] from a standalone byte slice.
+    pub fn from_bytes(bytes: &[u8]) -> Result<NpHeader, NpError> {
+        NpHeader::read(&mut Reader::new(bytes))
+    }
+
+    /// Serializes this header to its 37 byte big-endian representation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(37);
+        out.extend_from_slice(&self.np_version);
+        out.extend_from_slice(&self.packet_size.to_be_bytes());
+        out.extend_from_slice(&self.sequence_number.to_be_bytes());
+        out.extend_from_slice(&self.meta_sequence_number.to_be_bytes());
+        out.extend_from_slice(&self.start_time.to_be_bytes());
+        out.extend_from_slice(&self.latitude.to_be_bytes());
+        out.extend_from_slice(&self.longitude.to_be_bytes());
+        out.extend_from_slice(&self.altitude.to_be_bytes());
+        out.extend_from_slice(&self.data_source);
+        out.push(self.band_name);
+        out.extend_from_slice(&self.packet_extension_block.to_bytes());
+        out
+    }
+}
+
+impl NpPayloadHeader {
+    /// Reads the fixed 14 byte payload header.
+    ///
+    /// The `payload_size`/tag checks below only hold for the Steim1 seismic
+    /// time-series format; other media types (state-of-health, metadata,
+    /// logs) share the header's byte layout but not its size or field-tag
+    /// conventions, so they are only validated when `payload_media_type`
+    /// identifies a Steim1 body. Anything else is read through unchanged and
+    /// left for [`crate::NpPayloadKind::parse`] to classify.
+    fn read(r: &mut Reader<'_>) -> Result<NpPayloadHeader, NpError> {
+        let payload_size = r.u16()?;
+        if payload_size < 14 {
+            return Err(NpError::InconsistentSize {
+                field: "payload_size",
+                value: payload_size,
+            });
+        }
+        let payload_name = r.u8()?;
+        let payload_media_type = r.u8()?;
+        let payload_extension_block = NpExtensionBlock::from_bytes(r.take(2)?);
+        let number_samples: [u8; 4] = r.array()?;
+        let sample_rate: [u8; 4] = r.array()?;
+
+        if payload_media_type == MEDIA_STEIM1 {
+            if !PAYLOAD_SIZES.contains(&payload_size) {
+                return Err(NpError::InconsistentSize {
+                    field: "payload_size",
+                    value: payload_size,
+                });
+            }
+            let num_tag = u16::from_be_bytes([number_samples[0], number_samples[1]]);
+            if num_tag != TAG_NUM_SAMPLES {
+                return Err(NpError::BadTag {
+                    field: "number_samples",
+                    expected: TAG_NUM_SAMPLES,
+                    found: num_tag,
+                });
+            }
+            let rate_tag = u16::from_be_bytes([sample_rate[0], sample_rate[1]]);
+            if rate_tag != TAG_SAMPLE_RATE {
+                return Err(NpError::BadTag {
+                    field: "sample_rate",
+                    expected: TAG_SAMPLE_RATE,
+                    found: rate_tag,
+                });
+            }
+        }
+
+        Ok(NpPayloadHeader {
+            payload_size,
+            payload_name,
+            payload_media_type,
+            payload_extension_block,
+            number_samples,
+            sample_rate,
+        })
+    }
+
+    /// Parses an [`NpPayloadHeader`] from a standalone byte slice.
+    pub fn from_bytes(bytes: &[u8]) -> Result<NpPayloadHeader, NpError> {
+        NpPayloadHeader::read(&mut Reader::new(bytes))
+    }
+
+    /// Serializes this payload header to its 14 byte big-endian representation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(14);
+        out.extend_from_slice(&self.payload_size.to_be_bytes());
+        out.push(self.payload_name);
+        out.push(self.payload_media_type);
+        out.extend_from_slice(&self.payload_extension_block.to_bytes());
+        out.extend_from_slice(&self.number_samples);
+        out.extend_from_slice(&self.sample_rate);
+        out
+    }
+}
+
+impl NpPayload {
+    fn read(r: &mut Reader<'_>) -> Result<NpPayload, NpError> {
+        let header = NpPayloadHeader::read(r)?;
+        // The body is everything the payload size accounts for past the 14
+        // byte payload header.
+        let body_len = header.payload_size as usize - 14;
+        let body = r.take(body_len)?.to_vec();
+        Ok(NpPayload { header, body })
+    }
+
+    /// Parses an [`NpPayload`] from a standalone byte slice.
+    pub fn from_bytes(bytes: &[u8]) -> Result<NpPayload, NpError> {
+        NpPayload::read(&mut Reader::new(bytes))
+    }
+
+    /// Serializes this payload (header followed by body) to bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.header.to_bytes();
+        out.extend_from_slice(&self.body);
+        out
+    }
+}
+
+impl NpPacket {
+    /// Parses a complete [`NpPacket`] from `bytes`, honoring the `NP` magic
+    /// and the header's declared packet/payload sizes. The fixed `462`/`206`
+    /// payload sizes and tag-prefixed `numSamples`/`sampleRate` fields are
+    /// only checked in the payload header for the Steim1 media type. The
+    /// header's `packet_size` must still be `499` or `243` for every media
+    /// type and must equal `37 + payload_size`, so a payload of another media
+    /// type parses only when it is `462` or `206` bytes long; it is then read
+    /// through for [`crate::NpPayloadKind`] to classify.
+    pub fn from_bytes(bytes: &[u8]) -> Result<NpPacket, NpError> {
+        let mut r = Reader::new(bytes);
+        let header = NpHeader::read(&mut r)?;
+        let payload = NpPayload::read(&mut r)?;
+        // The header packet_size must account for the header plus the payload.
+        let expected = BODY_OFFSET - 14 + payload.header.payload_size as usize;
+        if header.packet_size as usize != expected {
+            return Err(NpError::InconsistentSize {
+                field: "packet_size",
+                value: header.packet_size,
+            });
+        }
+        Ok(NpPacket { header, payload })
+    }
+
+    /// Serializes a complete [`NpPacket`] (header followed by payload) to bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = self.header.to_bytes();
+        out.extend_from_slice(&self.payload.to_bytes());
+        out
+    }
+}
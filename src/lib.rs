@@ -14,14 +14,15 @@
 //! # Overview of NP packet format
 //! Each NP data packet contains the following information:
 //! - A BandId: identifies the stream of NP packets by datasource and band
-//! name.
+//!   name.
 //! - Sequence Number: packet sequence within the scope of the packet’s
-//! BandId.
+//!   BandId.
 //! - Metadata Sequence Number: identifies associated metadata packets.
 //! - Time: the time the data in the packet was generated, in nanoseconds
-//! since 1970 epoch.
+//!   since 1970 epoch.
 //! - Geographic Location.
 //! - Data payload.
+//!
 //! Taurus embeds NP packets in UDP packets for transmission via IP.
 //!
 //! # Seismic data packets
@@ -33,8 +34,23 @@
 //! a field Description, are applicable to seismic data packets as created on
 //! Taurus.
 
+pub mod ascii;
+pub mod codec;
+pub mod miniseed;
+pub mod payload;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod steim1;
+mod time;
+pub mod transport;
+
+pub use codec::NpError;
+pub use payload::{NpExtensionBlock, NpPayloadBody, NpPayloadKind, Steim1TimeSeries};
+pub use transport::{BandId, Demux, NpEvent, NpReceiver};
+
 /// ## NP Packet Header
 /// There is a single main header block for each packet.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NpHeader {
     /// ASCII characters `N` & `P` or `0x4E50`.
     pub np_version: [u8; 2],
@@ -78,14 +94,18 @@ pub struct NpHeader {
     /// `0x8D` for "band/timeseries3/"
     pub band_name: u8,
     /// Set to `0x00` `0x00` for seismic data to indicate not used.
-    pub packet_extension_block: i16,
+    ///
+    /// Parsed as a dictionary of tagged fields so that version-specific
+    /// extension fields carried by other media types are preserved.
+    pub packet_extension_block: NpExtensionBlock,
 }
 
-//! ## Data payload
-//! A seismic data payload is comprised of a fixed payload header block,
-//! containing 2 payload header extensions, and a payload body.
+// ## Data payload
+// A seismic data payload is comprised of a fixed payload header block,
+// containing 2 payload header extensions, and a payload body.
 
 /// ### Payload header
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NpPayloadHeader {
     /// Indicates the total size of the payload including itself, the rest of
     /// the header including the extension block, and the data;
@@ -104,7 +124,10 @@ pub struct NpPayloadHeader {
     /// payload;
     ///
     /// `0x00 0x08`
-    pub payload_extension_block: i16,
+    ///
+    /// Parsed as a dictionary of tagged fields (see
+    /// [`NpExtensionBlock`]).
+    pub payload_extension_block: NpExtensionBlock,
     /// Number of samples in this packet
     ///
     /// `0x05 0x87 numSamples numSamples`
@@ -119,19 +142,21 @@ pub struct NpPayloadHeader {
     pub sample_rate: [u8; 4],
 }
 
-//! ## Payload body
-//! The seismic data payload body consists of 3 or 7 frames of Steim1
-//! compressed data (without the 64 byte space for standard SEED headers) as
-//! described in Appendix B of 
-//! [the SEED manual](http://www.iris.edu/manuals/SEEDManual_V2.4.pdf).
-//! The receiving application must add the standard headers to produce a valid
-//! MiniSEED data record. The seismic data payload body starts at offset of 51.
+// ## Payload body
+// The seismic data payload body consists of 3 or 7 frames of Steim1
+// compressed data (without the 64 byte space for standard SEED headers) as
+// described in Appendix B of
+// [the SEED manual](http://www.iris.edu/manuals/SEEDManual_V2.4.pdf).
+// The receiving application must add the standard headers to produce a valid
+// MiniSEED data record. The seismic data payload body starts at offset of 51.
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NpPayload {
     pub header: NpPayloadHeader,
     pub body: Vec<u8>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NpPacket {
     pub header: NpHeader,
     pub payload: NpPayload,
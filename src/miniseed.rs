@@ -0,0 +1,235 @@
+//! Conversion of an [`NpPacket`](crate::NpPacket) into a valid MiniSEED data
+//! record.
+//!
+//! The NP payload body is already a sequence of Steim1 frames; what it lacks
+//! are the standard SEED headers. [`NpPacket::to_miniseed`] prepends the 48
+//! byte fixed data header, a [Blockette 1000] (data-only SEED blockette) and a
+//! [Blockette 1001] (data extension), producing a 512 byte record that the
+//! standard SEED/ObsPy toolchain can read directly.
+//!
+//! [Blockette 1000]: http://www.iris.edu/manuals/SEEDManual_V2.4.pdf
+//! [Blockette 1001]: http://www.iris.edu/manuals/SEEDManual_V2.4.pdf
+
+use crate::NpPacket;
+
+/// Length of the fixed SEED data header.
+const FIXED_HEADER_LEN: usize = 48;
+/// Record length of the records we emit (512 bytes).
+const RECORD_LEN: usize = 512;
+/// `log2(512)`, the record-length exponent stored in Blockette 1000.
+const RECORD_LEN_EXP: u8 = 9;
+/// SEED encoding format code for Steim1.
+const ENCODING_STEIM1: u8 = 10;
+/// SEED word order code for big-endian (most significant byte first).
+const WORD_ORDER_BE: u8 = 1;
+/// Offset of the first blockette (immediately after the fixed header).
+const FIRST_BLOCKETTE_OFFSET: u16 = FIXED_HEADER_LEN as u16;
+/// Offset at which the data (Steim1 frames) begins: past both blockettes.
+const DATA_OFFSET: u16 = FIXED_HEADER_LEN as u16 + 8 + 8;
+
+/// A broken-down UTC time, as carried by a SEED `BTIME` field.
+struct BTime {
+    year: u16,
+    day_of_year: u16,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    /// Fraction of a second in units of 0.0001 s (`0..=9999`).
+    tenths_of_ms: u16,
+    /// Residual microseconds beyond the 0.0001 s resolution, for Blockette 1001.
+    microseconds: i8,
+}
+
+/// Converts nanoseconds since the 1970 epoch into a SEED `BTIME`.
+///
+/// `tenths_of_ms` only resolves time to `0.0001 s` (100 µs); the Blockette
+/// 1001 `microseconds` field carries the signed remainder so the pair
+/// reconstructs the original time to the microsecond instead of always
+/// rounding down; nanoseconds below one microsecond are dropped.
+fn ns_to_btime(ns: u64) -> BTime {
+    let mut total_secs = (ns / 1_000_000_000) as i64;
+    let total_us = (ns % 1_000_000_000) / 1_000;
+    let mut tenths_of_ms = (total_us / 100) as i64;
+    let residual = (total_us % 100) as i64;
+    let microseconds = if residual < 50 {
+        residual
+    } else {
+        tenths_of_ms += 1;
+        residual - 100
+    };
+    if tenths_of_ms == 10_000 {
+        tenths_of_ms = 0;
+        total_secs += 1;
+    }
+
+    let days = total_secs.div_euclid(86_400);
+    let secs_of_day = total_secs.rem_euclid(86_400);
+
+    let (year, _month, _day) = crate::time::civil_from_days(days);
+    let day_of_year = (days - crate::time::days_from_civil(year, 1, 1) + 1) as u16;
+
+    BTime {
+        year: year as u16,
+        day_of_year,
+        hour: (secs_of_day / 3600) as u8,
+        minute: ((secs_of_day % 3600) / 60) as u8,
+        second: (secs_of_day % 60) as u8,
+        tenths_of_ms: tenths_of_ms as u16,
+        microseconds: microseconds as i8,
+    }
+}
+
+/// Writes a fixed-width ASCII field, left-justified and space-padded.
+fn write_ascii(out: &mut Vec<u8>, text: &str, width: usize) {
+    let bytes = text.as_bytes();
+    for i in 0..width {
+        out.push(bytes.get(i).copied().unwrap_or(b' '));
+    }
+}
+
+impl NpPacket {
+    /// Builds a 512 byte MiniSEED data record from this packet.
+    ///
+    /// The fixed header's start time is taken from `start_time`
+    /// (nanoseconds since epoch), the sample count from `number_samples`, and
+    /// the sample-rate factor/multiplier from `sample_rate`. The Steim1 body
+    /// is copied verbatim and the record is zero-padded to 512 bytes.
+    pub fn to_miniseed(
+        &self,
+        station: &str,
+        network: &str,
+        channel: &str,
+        location: &str,
+    ) -> Vec<u8> {
+        let number_samples =
+            u16::from_be_bytes([self.payload.header.number_samples[2], self.payload.header.number_samples[3]]);
+        let sample_rate =
+            u16::from_be_bytes([self.payload.header.sample_rate[2], self.payload.header.sample_rate[3]]);
+        let btime = ns_to_btime(self.header.start_time);
+
+        let mut out = Vec::with_capacity(RECORD_LEN);
+
+        // --- Fixed data header (48 bytes) ---
+        write_ascii(&mut out, "000001", 6); // sequence number
+        out.push(b'D'); // data header/quality indicator
+        out.push(b' '); // reserved
+        write_ascii(&mut out, station, 5);
+        write_ascii(&mut out, location, 2);
+        write_ascii(&mut out, channel, 3);
+        write_ascii(&mut out, network, 2);
+        // BTIME (10 bytes)
+        out.extend_from_slice(&btime.year.to_be_bytes());
+        out.extend_from_slice(&btime.day_of_year.to_be_bytes());
+        out.push(btime.hour);
+        out.push(btime.minute);
+        out.push(btime.second);
+        out.push(0); // unused
+        out.extend_from_slice(&btime.tenths_of_ms.to_be_bytes());
+        out.extend_from_slice(&number_samples.to_be_bytes());
+        // Integer sample rate: factor is samples-per-second, multiplier 1.
+        out.extend_from_slice(&(sample_rate as i16).to_be_bytes());
+        out.extend_from_slice(&1i16.to_be_bytes());
+        out.push(0); // activity flags
+        out.push(0); // I/O and clock flags
+        out.push(0); // data quality flags
+        out.push(2); // number of blockettes that follow
+        out.extend_from_slice(&0i32.to_be_bytes()); // time correction
+        out.extend_from_slice(&DATA_OFFSET.to_be_bytes()); // beginning of data
+        out.extend_from_slice(&FIRST_BLOCKETTE_OFFSET.to_be_bytes()); // first blockette
+
+        // --- Blockette 1000 (8 bytes) ---
+        out.extend_from_slice(&1000u16.to_be_bytes());
+        out.extend_from_slice(&(FIRST_BLOCKETTE_OFFSET + 8).to_be_bytes()); // next blockette
+        out.push(ENCODING_STEIM1);
+        out.push(WORD_ORDER_BE);
+        out.push(RECORD_LEN_EXP);
+        out.push(0); // reserved
+
+        // --- Blockette 1001 (8 bytes) ---
+        out.extend_from_slice(&1001u16.to_be_bytes());
+        out.extend_from_slice(&0u16.to_be_bytes()); // next blockette (none)
+        out.push(0); // timing quality
+        out.push(btime.microseconds as u8);
+        out.push(0); // reserved
+        out.push((self.payload.body.len() / 64) as u8); // frame count
+
+        // --- Data ---
+        out.extend_from_slice(&self.payload.body);
+        out.resize(RECORD_LEN, 0);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{steim1, NpExtensionBlock, NpHeader, NpPacket, NpPayload, NpPayloadHeader};
+
+    #[test]
+    fn centers_microsecond_residual_into_a_signed_offset() {
+        // 90 us past the second is closer to the *next* 0.0001 s tick (100 us)
+        // than the current one, so it should round forward with a negative
+        // residual rather than truncate down to tenths_of_ms = 0.
+        let btime = ns_to_btime(90_000);
+        assert_eq!(btime.tenths_of_ms, 1);
+        assert_eq!(btime.microseconds, -10);
+
+        // 30 us rounds down to the current tick with a positive residual.
+        let btime = ns_to_btime(30_000);
+        assert_eq!(btime.tenths_of_ms, 0);
+        assert_eq!(btime.microseconds, 30);
+    }
+
+    fn packet_with_samples(samples: &[i32], start_time: u64) -> NpPacket {
+        let mut body = steim1::encode(samples);
+        body.resize(3 * 64, 0);
+        NpPacket {
+            header: NpHeader {
+                np_version: [0x4E, 0x50],
+                packet_size: 243,
+                sequence_number: 0,
+                meta_sequence_number: -1,
+                start_time,
+                latitude: 0,
+                longitude: 0,
+                altitude: 0,
+                data_source: [0xE8, 11, 0, 1],
+                band_name: 0x89,
+                packet_extension_block: NpExtensionBlock::default(),
+            },
+            payload: NpPayload {
+                header: NpPayloadHeader {
+                    payload_size: 206,
+                    payload_name: 0,
+                    payload_media_type: 0x83,
+                    payload_extension_block: NpExtensionBlock::default(),
+                    number_samples: [0x05, 0x87, 0, samples.len() as u8],
+                    sample_rate: [0x05, 0x85, 0, 100],
+                },
+                body,
+            },
+        }
+    }
+
+    #[test]
+    fn record_is_512_bytes_with_blockettes_at_the_documented_offsets() {
+        let packet = packet_with_samples(&[1, 2, 3], 1_600_000_000_090_000_000);
+        let record = packet.to_miniseed("STA", "NET", "CHZ", "00");
+
+        assert_eq!(record.len(), RECORD_LEN);
+        assert_eq!(u16::from_be_bytes([record[44], record[45]]), DATA_OFFSET);
+        assert_eq!(
+            u16::from_be_bytes([record[46], record[47]]),
+            FIRST_BLOCKETTE_OFFSET
+        );
+        assert_eq!(
+            u16::from_be_bytes([record[FIRST_BLOCKETTE_OFFSET as usize], record[FIRST_BLOCKETTE_OFFSET as usize + 1]]),
+            1000
+        );
+        assert_eq!(
+            u16::from_be_bytes([record[FIRST_BLOCKETTE_OFFSET as usize + 8], record[FIRST_BLOCKETTE_OFFSET as usize + 9]]),
+            1001
+        );
+        assert_eq!(&record[DATA_OFFSET as usize..DATA_OFFSET as usize + packet.payload.body.len()], &packet.payload.body[..]);
+    }
+}
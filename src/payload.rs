@@ -0,0 +1,128 @@
+//! Version-aware dispatch for payload bodies and extension blocks.
+//!
+//! The NP format carries many payload media types (Steim1 time-series,
+//! state-of-health, metadata/configuration, logs and triggers) as well as
+//! extension blocks that vary by version. Rather than hard-coding the seismic
+//! time-series case, parsing is organized around the [`NpPayloadBody`] trait
+//! and the [`NpPayloadKind`] enum, which dispatch on
+//! [`payload_media_type`](crate::NpPayloadHeader::payload_media_type). A new
+//! payload format is added by implementing [`NpPayloadBody`] and registering a
+//! variant in [`NpPayloadKind::parse`] — the core parser does not change.
+
+use crate::steim1::{self, Steim1Error};
+use crate::{NpPayload, NpPayloadHeader};
+
+/// An extension block, parsed as a dictionary of tagged fields.
+///
+/// Each field is a `(tag, value)` byte pair. For seismic data packets the
+/// block is a single pair — `0x00 0x00` for the packet extension and
+/// `0x00 0x08` for the payload extension — but the representation generalizes
+/// to the version-specific blocks carried by other media types.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NpExtensionBlock {
+    /// The tagged fields, in wire order.
+    pub fields: Vec<(u8, u8)>,
+}
+
+impl NpExtensionBlock {
+    /// Interprets a raw extension block as a sequence of `(tag, value)` pairs.
+    pub fn from_bytes(bytes: &[u8]) -> NpExtensionBlock {
+        let fields = bytes.chunks_exact(2).map(|c| (c[0], c[1])).collect();
+        NpExtensionBlock { fields }
+    }
+
+    /// Serializes the tagged fields back to their raw byte form.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.fields.len() * 2);
+        for &(tag, value) in &self.fields {
+            out.push(tag);
+            out.push(value);
+        }
+        out
+    }
+
+    /// Returns the value associated with `tag`, if present.
+    pub fn get(&self, tag: u8) -> Option<u8> {
+        self.fields.iter().find(|&&(t, _)| t == tag).map(|&(_, v)| v)
+    }
+}
+
+/// A payload body that knows how to identify and parse itself.
+///
+/// Implement this trait to teach the crate a new payload media type; the
+/// variant is then wired into [`NpPayloadKind::parse`].
+pub trait NpPayloadBody: Sized {
+    /// The `payload_media_type` byte that selects this body.
+    fn media_type() -> u8;
+
+    /// Parses the body from its raw bytes and the already-parsed header.
+    fn parse(bytes: &[u8], header: &NpPayloadHeader) -> Self;
+}
+
+/// A Steim1 encoded time-series body (`payload_media_type` `0x83`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Steim1TimeSeries {
+    /// The raw Steim1 frames, as they appear on the wire.
+    pub body: Vec<u8>,
+}
+
+impl NpPayloadBody for Steim1TimeSeries {
+    fn media_type() -> u8 {
+        0x83
+    }
+
+    fn parse(bytes: &[u8], _header: &NpPayloadHeader) -> Self {
+        Steim1TimeSeries { body: bytes.to_vec() }
+    }
+}
+
+impl Steim1TimeSeries {
+    /// Decodes the frames into integer samples.
+    pub fn decode(&self, num_samples: u32) -> Result<Vec<i32>, Steim1Error> {
+        steim1::decode(&self.body, num_samples)
+    }
+}
+
+/// A parsed payload body, dispatched on the media type.
+///
+/// New media types (state-of-health, metadata/config packets identified via
+/// `meta_sequence_number`, logs and triggers) are added as further variants;
+/// anything not yet registered is preserved verbatim as
+/// [`NpPayloadKind::Unknown`] so the packet still round-trips.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NpPayloadKind {
+    /// Steim1 encoded time-series samples.
+    Steim1TimeSeries(Steim1TimeSeries),
+    /// A media type this crate does not yet decode, kept as raw bytes.
+    Unknown { media_type: u8, raw: Vec<u8> },
+}
+
+impl NpPayloadKind {
+    /// Classifies a raw payload body using the header's media type.
+    pub fn parse(header: &NpPayloadHeader, body: &[u8]) -> NpPayloadKind {
+        match header.payload_media_type {
+            t if t == Steim1TimeSeries::media_type() => {
+                NpPayloadKind::Steim1TimeSeries(Steim1TimeSeries::parse(body, header))
+            }
+            t => NpPayloadKind::Unknown {
+                media_type: t,
+                raw: body.to_vec(),
+            },
+        }
+    }
+
+    /// The `payload_media_type` byte for this kind.
+    pub fn media_type(&self) -> u8 {
+        match self {
+            NpPayloadKind::Steim1TimeSeries(_) => Steim1TimeSeries::media_type(),
+            NpPayloadKind::Unknown { media_type, .. } => *media_type,
+        }
+    }
+}
+
+impl NpPayload {
+    /// Classifies the payload body according to its media type.
+    pub fn kind(&self) -> NpPayloadKind {
+        NpPayloadKind::parse(&self.header, &self.body)
+    }
+}
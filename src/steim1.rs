@@ -0,0 +1,256 @@
+//! Steim1 compression codec for the seismic data payload body.
+//!
+//! `NpPayload.body` holds 3 or 7 frames of Steim1-compressed time series
+//! (without the 64 byte space reserved for standard SEED headers) as described
+//! in Appendix B of
+//! [the SEED manual](http://www.iris.edu/manuals/SEEDManual_V2.4.pdf). This
+//! module turns those frames into integer samples and back.
+//!
+//! Steim1 organizes data into 64 byte frames, each holding sixteen big-endian
+//! 32-bit words. Word `0` of every frame is a *control word* carrying sixteen
+//! 2-bit nibbles (one per word, most-significant first):
+//! - `0` — non-data / control word,
+//! - `1` — four 8-bit differences packed in the word,
+//! - `2` — two 16-bit differences,
+//! - `3` — one 32-bit difference.
+//!
+//! In the very first frame words `1` and `2` are reserved: word `1` is `X0`
+//! (the forward integration constant, equal to the first sample) and word `2`
+//! is `Xn` (the reverse constant, equal to the last sample), so their nibbles
+//! are `0`.
+
+/// The number of bytes in a single Steim1 frame.
+const FRAME_BYTES: usize = 64;
+/// The number of 32-bit words in a single Steim1 frame.
+const FRAME_WORDS: usize = 16;
+
+/// Errors produced while decoding a Steim1 body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Steim1Error {
+    /// The body length is not a whole number of 64 byte frames.
+    BadFrameLength(usize),
+    /// The frames did not expand to the requested number of samples.
+    TooFewSamples { requested: u32, found: usize },
+    /// The running reconstruction did not match the reverse constant `Xn`.
+    IntegrityCheckFailed { expected: i32, found: i32 },
+}
+
+impl std::fmt::Display for Steim1Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Steim1Error::BadFrameLength(len) => {
+                write!(f, "body length {len} is not a multiple of 64 bytes")
+            }
+            Steim1Error::TooFewSamples { requested, found } => {
+                write!(f, "expected {requested} samples but only {found} were decoded")
+            }
+            Steim1Error::IntegrityCheckFailed { expected, found } => {
+                write!(f, "reverse integration constant mismatch: expected {expected}, found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Steim1Error {}
+
+/// Reads the big-endian 32-bit word at `index` within `frame`.
+fn word(frame: &[u8], index: usize) -> u32 {
+    let off = index * 4;
+    u32::from_be_bytes([frame[off], frame[off + 1], frame[off + 2], frame[off + 3]])
+}
+
+/// Returns the 2-bit nibble for word `index` from the control word `ctrl`.
+fn nibble(ctrl: u32, index: usize) -> u32 {
+    (ctrl >> (2 * (FRAME_WORDS - 1 - index))) & 0x3
+}
+
+/// Expands a single data word into its differences, appending them to `out`.
+fn expand(value: u32, nib: u32, out: &mut Vec<i32>) {
+    match nib {
+        1 => {
+            for byte in value.to_be_bytes() {
+                out.push(i32::from(byte as i8));
+            }
+        }
+        2 => {
+            let bytes = value.to_be_bytes();
+            out.push(i32::from(i16::from_be_bytes([bytes[0], bytes[1]])));
+            out.push(i32::from(i16::from_be_bytes([bytes[2], bytes[3]])));
+        }
+        3 => out.push(value as i32),
+        _ => {}
+    }
+}
+
+/// Decodes `num_samples` integer samples from a Steim1 `body`.
+///
+/// Decoding walks the words of every frame in order, expanding each word into
+/// differences according to its nibble. Reconstruction runs an accumulator
+/// starting from `X0`: the first expanded difference is the delta into `X0`
+/// (the difference from the previous record's last sample) and is discarded,
+/// after which each subsequent difference is added to the running total to
+/// yield the next sample. The final reconstructed value is asserted to equal
+/// `Xn` as an integrity check.
+pub fn decode(body: &[u8], num_samples: u32) -> Result<Vec<i32>, Steim1Error> {
+    if !body.len().is_multiple_of(FRAME_BYTES) {
+        return Err(Steim1Error::BadFrameLength(body.len()));
+    }
+    if num_samples == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut x0 = 0i32;
+    let mut xn = 0i32;
+    let mut diffs: Vec<i32> = Vec::with_capacity(num_samples as usize);
+
+    'frames: for (frame_index, frame) in body.chunks_exact(FRAME_BYTES).enumerate() {
+        let ctrl = word(frame, 0);
+        for w in 1..FRAME_WORDS {
+            // In the first frame words 1 and 2 carry X0 and Xn, not data.
+            if frame_index == 0 && (w == 1 || w == 2) {
+                if w == 1 {
+                    x0 = word(frame, w) as i32;
+                } else {
+                    xn = word(frame, w) as i32;
+                }
+                continue;
+            }
+            expand(word(frame, w), nibble(ctrl, w), &mut diffs);
+            if diffs.len() >= num_samples as usize {
+                break 'frames;
+            }
+        }
+    }
+
+    if diffs.len() < num_samples as usize {
+        return Err(Steim1Error::TooFewSamples {
+            requested: num_samples,
+            found: diffs.len(),
+        });
+    }
+
+    let mut samples = Vec::with_capacity(num_samples as usize);
+    let mut running = x0;
+    samples.push(running);
+    // diffs[0] is the delta into X0 and is discarded; accumulate the rest.
+    for diff in diffs.iter().skip(1).take(num_samples as usize - 1) {
+        running += diff;
+        samples.push(running);
+    }
+
+    if running != xn {
+        return Err(Steim1Error::IntegrityCheckFailed {
+            expected: xn,
+            found: running,
+        });
+    }
+
+    Ok(samples)
+}
+
+/// Whether a difference fits an 8-bit slot.
+fn fits_i8(d: i32) -> bool {
+    i8::try_from(d).is_ok()
+}
+
+fn fits_i16(d: i32) -> bool {
+    i16::try_from(d).is_ok()
+}
+
+/// Encodes integer `samples` into Steim1 frames.
+///
+/// Encoding is the inverse of [`decode`]: it computes first differences (the
+/// leading difference being the sample itself, i.e. the delta from an implied
+/// previous value of `0`), greedily packs them into words, preferring four
+/// 8-bit slots, then two 16-bit slots, then one 32-bit slot, whichever the
+/// next differences fit first, sets the nibbles and control words accordingly, and writes
+/// `X0`/`Xn` into frame `0`.
+pub fn encode(samples: &[i32]) -> Vec<u8> {
+    if samples.is_empty() {
+        return Vec::new();
+    }
+
+    // First differences. diffs[0] is the delta from an implied 0.
+    let mut diffs = Vec::with_capacity(samples.len());
+    diffs.push(samples[0]);
+    for pair in samples.windows(2) {
+        diffs.push(pair[1] - pair[0]);
+    }
+
+    // Greedily group differences into words, recording (nibble, words-worth).
+    let mut words: Vec<(u32, u32)> = Vec::new();
+    let mut i = 0;
+    while i < diffs.len() {
+        if i + 4 <= diffs.len() && diffs[i..i + 4].iter().all(|&d| fits_i8(d)) {
+            let bytes = [
+                diffs[i] as i8 as u8,
+                diffs[i + 1] as i8 as u8,
+                diffs[i + 2] as i8 as u8,
+                diffs[i + 3] as i8 as u8,
+            ];
+            words.push((1, u32::from_be_bytes(bytes)));
+            i += 4;
+        } else if i + 2 <= diffs.len() && diffs[i..i + 2].iter().all(|&d| fits_i16(d)) {
+            let a = (diffs[i] as i16).to_be_bytes();
+            let b = (diffs[i + 1] as i16).to_be_bytes();
+            words.push((2, u32::from_be_bytes([a[0], a[1], b[0], b[1]])));
+            i += 2;
+        } else {
+            words.push((3, diffs[i] as u32));
+            i += 1;
+        }
+    }
+
+    // Emit frames, reserving words 1 and 2 of frame 0 for X0/Xn.
+    let x0 = samples[0] as u32;
+    let xn = *samples.last().unwrap() as u32;
+
+    let mut frames: Vec<[u32; FRAME_WORDS]> = Vec::new();
+    let mut frame = [0u32; FRAME_WORDS];
+    let mut ctrl = 0u32;
+
+    let mut push_frame = |frame: &mut [u32; FRAME_WORDS], ctrl: &mut u32| {
+        frame[0] = *ctrl;
+        frames.push(*frame);
+        *frame = [0u32; FRAME_WORDS];
+        *ctrl = 0;
+    };
+
+    // Frame 0 reserves slots 1 and 2 for the integration constants.
+    frame[1] = x0;
+    frame[2] = xn;
+    let mut slot = 3usize;
+
+    for (nib, value) in words {
+        if slot >= FRAME_WORDS {
+            push_frame(&mut frame, &mut ctrl);
+            slot = 1;
+        }
+        frame[slot] = value;
+        ctrl |= nib << (2 * (FRAME_WORDS - 1 - slot));
+        slot += 1;
+    }
+    push_frame(&mut frame, &mut ctrl);
+
+    let mut out = Vec::with_capacity(frames.len() * FRAME_BYTES);
+    for frame in frames {
+        for w in frame {
+            out.extend_from_slice(&w.to_be_bytes());
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_small_series() {
+        let samples: Vec<i32> = vec![12, 15, 9, 9, 100, -40, -41, 1000, 1001, 1002];
+        let body = encode(&samples);
+        assert_eq!(body.len() % FRAME_BYTES, 0);
+        let decoded = decode(&body, samples.len() as u32).unwrap();
+        assert_eq!(decoded, samples);
+    }
+}
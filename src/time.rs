@@ -0,0 +1,50 @@
+//! Small UTC calendar helpers shared by the output formats.
+//!
+//! These avoid a `chrono`/`time` dependency for the handful of conversions the
+//! MiniSEED and ASCII writers need.
+
+/// Days from `1970-01-01` to the civil date `y-m-d`, after Howard Hinnant's
+/// `days_from_civil`.
+pub(crate) fn days_from_civil(y: i64, m: i64, d: i64) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// The civil date `(year, month, day)` for a day count since `1970-01-01`,
+/// after Howard Hinnant's `civil_from_days`.
+pub(crate) fn civil_from_days(z: i64) -> (i64, i64, i64) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let y = yoe + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    (if m <= 2 { y + 1 } else { y }, m, d)
+}
+
+/// Renders nanoseconds since the 1970 epoch as
+/// `YYYY-MM-DDThh:mm:ss.ssssss` (microsecond precision).
+pub(crate) fn format_timestamp(ns: u64) -> String {
+    let total_secs = (ns / 1_000_000_000) as i64;
+    let micros = (ns % 1_000_000_000) / 1_000;
+    let days = total_secs.div_euclid(86_400);
+    let secs_of_day = total_secs.rem_euclid(86_400);
+    let (y, m, d) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}",
+        y,
+        m,
+        d,
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60,
+        micros,
+    )
+}
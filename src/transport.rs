@@ -0,0 +1,320 @@
+//! UDP reception and per-band reassembly.
+//!
+//! Taurus embeds NP packets in UDP datagrams for transmission over IP. This
+//! module adds the transport layer: an [`NpReceiver`] binds a socket, parses
+//! each datagram into an [`NpPacket`](crate::NpPacket), and demultiplexes the
+//! stream by [`BandId`] — the `(data_source, band_name)` pair that identifies
+//! a band. Within a band the [`sequence_number`](crate::NpHeader::sequence_number)
+//! orders packets, so the receiver can reorder late arrivals, drop duplicates,
+//! and report gaps, while [`meta_sequence_number`](crate::NpHeader::meta_sequence_number)
+//! tracks the metadata packet currently associated with each band.
+//!
+//! The reassembly policy lives in [`Demux`], which is independent of the
+//! socket so it can be driven from captured datagrams in tests.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::io;
+use std::net::{ToSocketAddrs, UdpSocket};
+
+use crate::NpPacket;
+
+/// Identifies a band: the originating device and its band name.
+///
+/// This is the demultiplexing key — every packet belongs to exactly one band,
+/// and sequence numbers are scoped to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BandId {
+    /// The URI of the device which produced the data.
+    pub data_source: [u8; 4],
+    /// The band to which the packet belongs.
+    pub band_name: u8,
+}
+
+impl BandId {
+    /// The [`BandId`] a packet belongs to.
+    pub fn of(packet: &NpPacket) -> BandId {
+        BandId {
+            data_source: packet.header.data_source,
+            band_name: packet.header.band_name,
+        }
+    }
+}
+
+/// An event yielded while reassembling a band.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NpEvent {
+    /// An in-order, de-duplicated packet ready for the application.
+    Packet(NpPacket),
+    /// A run of sequence numbers `from..to` was skipped and declared lost.
+    Gap { band: BandId, from: i32, to: i32 },
+    /// A packet whose sequence number had already been delivered or is still
+    /// buffered ahead of the cursor.
+    Duplicate { band: BandId, sequence_number: i32 },
+}
+
+/// Per-band reassembly state.
+///
+/// The cursor is the next sequence number expected. Packets below the cursor,
+/// or already buffered, are duplicates; the packet at the cursor is
+/// delivered immediately and the cursor advances over any contiguous packets
+/// held in the reorder buffer; packets ahead of the cursor are buffered until
+/// the gap before them is filled or [`flush`](Demux::flush) gives up on it.
+#[derive(Debug, Default)]
+struct BandStream {
+    /// The next sequence number expected in order, once the band has started.
+    next_seq: Option<i32>,
+    /// Packets received ahead of the cursor, keyed by sequence number.
+    buffer: BTreeMap<i32, NpPacket>,
+    /// The metadata sequence number currently associated with the band.
+    meta_sequence_number: Option<i32>,
+}
+
+impl BandStream {
+    /// Accepts a packet, appending any resulting events to `out`.
+    fn push(&mut self, band: BandId, packet: NpPacket, out: &mut Vec<NpEvent>) {
+        let meta = packet.header.meta_sequence_number;
+        if meta != -1 {
+            self.meta_sequence_number = Some(meta);
+        }
+        let seq = packet.header.sequence_number;
+        match self.next_seq {
+            None => {
+                out.push(NpEvent::Packet(packet));
+                self.next_seq = Some(seq.wrapping_add(1));
+                self.drain(out);
+            }
+            Some(expected) => {
+                if seq < expected || self.buffer.contains_key(&seq) {
+                    out.push(NpEvent::Duplicate { band, sequence_number: seq });
+                } else if seq == expected {
+                    out.push(NpEvent::Packet(packet));
+                    self.next_seq = Some(seq.wrapping_add(1));
+                    self.drain(out);
+                } else {
+                    self.buffer.insert(seq, packet);
+                }
+            }
+        }
+    }
+
+    /// Delivers packets from the buffer as long as they are contiguous with
+    /// the cursor.
+    fn drain(&mut self, out: &mut Vec<NpEvent>) {
+        while let Some(expected) = self.next_seq {
+            match self.buffer.remove(&expected) {
+                Some(packet) => {
+                    out.push(NpEvent::Packet(packet));
+                    self.next_seq = Some(expected.wrapping_add(1));
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Gives up on the packets before the earliest buffered one, emitting a
+    /// [`NpEvent::Gap`] and delivering whatever follows.
+    fn flush(&mut self, band: BandId, out: &mut Vec<NpEvent>) {
+        if let (Some(expected), Some(&next)) = (self.next_seq, self.buffer.keys().next()) {
+            if next > expected {
+                out.push(NpEvent::Gap { band, from: expected, to: next });
+                self.next_seq = Some(next);
+                self.drain(out);
+            }
+        }
+    }
+}
+
+/// Demultiplexes a mixed stream of packets into ordered per-band streams.
+///
+/// This is the socket-independent core: feed it packets with [`push`](Demux::push)
+/// in arrival order and it returns the [`NpEvent`]s that become deliverable.
+#[derive(Debug, Default)]
+pub struct Demux {
+    bands: HashMap<BandId, BandStream>,
+}
+
+impl Demux {
+    /// Creates an empty demultiplexer.
+    pub fn new() -> Demux {
+        Demux::default()
+    }
+
+    /// Routes a packet to its band and returns any events it produces.
+    pub fn push(&mut self, packet: NpPacket) -> Vec<NpEvent> {
+        let band = BandId::of(&packet);
+        let mut out = Vec::new();
+        self.bands.entry(band).or_default().push(band, packet, &mut out);
+        out
+    }
+
+    /// Declares a gap in every band that is waiting on a missing packet,
+    /// releasing the packets buffered behind it. Call this when a band has
+    /// been quiet long enough that the missing packets are presumed lost.
+    pub fn flush(&mut self) -> Vec<NpEvent> {
+        let mut out = Vec::new();
+        for (&band, stream) in &mut self.bands {
+            stream.flush(band, &mut out);
+        }
+        out
+    }
+
+    /// The metadata sequence number currently associated with `band`, if one
+    /// has been seen.
+    pub fn metadata(&self, band: &BandId) -> Option<i32> {
+        self.bands.get(band).and_then(|s| s.meta_sequence_number)
+    }
+}
+
+/// A UDP acquisition client that yields contiguous, de-duplicated packets.
+///
+/// Each datagram is parsed into an [`NpPacket`] and fed through a [`Demux`];
+/// [`recv`](NpReceiver::recv) returns the next reassembly event, reading more
+/// datagrams as needed. The receiver also implements [`Iterator`], yielding
+/// `io::Result<NpEvent>`.
+pub struct NpReceiver {
+    socket: UdpSocket,
+    demux: Demux,
+    pending: VecDeque<NpEvent>,
+    buf: Vec<u8>,
+}
+
+impl NpReceiver {
+    /// Binds a UDP socket to `addr` and starts receiving.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<NpReceiver> {
+        let socket = UdpSocket::bind(addr)?;
+        Ok(NpReceiver::from_socket(socket))
+    }
+
+    /// Wraps an already-bound socket, for callers that need to configure it
+    /// (multicast groups, timeouts) before receiving.
+    pub fn from_socket(socket: UdpSocket) -> NpReceiver {
+        NpReceiver {
+            socket,
+            demux: Demux::new(),
+            pending: VecDeque::new(),
+            // Large enough for the biggest seismic packet (499 bytes).
+            buf: vec![0u8; 2048],
+        }
+    }
+
+    /// The underlying demultiplexer, for inspecting per-band metadata.
+    pub fn demux(&self) -> &Demux {
+        &self.demux
+    }
+
+    /// Returns the next reassembly event, reading datagrams until one becomes
+    /// deliverable. A datagram that fails to parse is surfaced as an
+    /// [`io::ErrorKind::InvalidData`] error.
+    pub fn recv(&mut self) -> io::Result<NpEvent> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(event);
+            }
+            let (len, _addr) = self.socket.recv_from(&mut self.buf)?;
+            let packet = NpPacket::from_bytes(&self.buf[..len])
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            self.pending.extend(self.demux.push(packet));
+        }
+    }
+}
+
+impl Iterator for NpReceiver {
+    type Item = io::Result<NpEvent>;
+
+    fn next(&mut self) -> Option<io::Result<NpEvent>> {
+        Some(self.recv())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(data_source: [u8; 4], band_name: u8, seq: i32) -> NpPacket {
+        crate::NpPacket {
+            header: crate::NpHeader {
+                np_version: [0x4E, 0x50],
+                packet_size: 243,
+                sequence_number: seq,
+                meta_sequence_number: -1,
+                start_time: 0,
+                latitude: 0,
+                longitude: 0,
+                altitude: 0,
+                data_source,
+                band_name,
+                packet_extension_block: crate::NpExtensionBlock::default(),
+            },
+            payload: crate::NpPayload {
+                header: crate::NpPayloadHeader {
+                    payload_size: 206,
+                    payload_name: 0,
+                    payload_media_type: 0x83,
+                    payload_extension_block: crate::NpExtensionBlock::default(),
+                    number_samples: [0x05, 0x87, 0, 0],
+                    sample_rate: [0x05, 0x85, 0, 100],
+                },
+                body: Vec::new(),
+            },
+        }
+    }
+
+    fn seqs(events: Vec<NpEvent>) -> Vec<i32> {
+        events
+            .into_iter()
+            .filter_map(|e| match e {
+                NpEvent::Packet(p) => Some(p.header.sequence_number),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reorders_within_a_band() {
+        let src = [0xE8, 11, 0, 1];
+        let mut demux = Demux::new();
+        assert_eq!(seqs(demux.push(packet(src, 0x89, 0))), vec![0]);
+        // 2 arrives before 1 and is held until 1 fills the gap.
+        assert!(seqs(demux.push(packet(src, 0x89, 2))).is_empty());
+        assert_eq!(seqs(demux.push(packet(src, 0x89, 1))), vec![1, 2]);
+    }
+
+    #[test]
+    fn reports_duplicates() {
+        let src = [0xE8, 11, 0, 1];
+        let mut demux = Demux::new();
+        demux.push(packet(src, 0x89, 0));
+        demux.push(packet(src, 0x89, 1));
+        let events = demux.push(packet(src, 0x89, 1));
+        assert_eq!(
+            events,
+            vec![NpEvent::Duplicate {
+                band: BandId { data_source: src, band_name: 0x89 },
+                sequence_number: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn flush_declares_a_gap() {
+        let src = [0xE8, 11, 0, 1];
+        let band = BandId { data_source: src, band_name: 0x89 };
+        let mut demux = Demux::new();
+        demux.push(packet(src, 0x89, 0));
+        // 1 never arrives; 2 and 3 wait.
+        demux.push(packet(src, 0x89, 2));
+        demux.push(packet(src, 0x89, 3));
+        let events = demux.flush();
+        assert_eq!(events[0], NpEvent::Gap { band, from: 1, to: 2 });
+        assert_eq!(seqs(events), vec![2, 3]);
+    }
+
+    #[test]
+    fn separates_bands() {
+        let a = [0xE8, 11, 0, 1];
+        let b = [0xE8, 13, 0, 2];
+        let mut demux = Demux::new();
+        assert_eq!(seqs(demux.push(packet(a, 0x89, 5))), vec![5]);
+        assert_eq!(seqs(demux.push(packet(b, 0x8B, 0))), vec![0]);
+    }
+}